@@ -1,9 +1,12 @@
+mod retry;
 mod rpc;
+mod rt;
 mod runtime;
 
 #[cfg(not(test))] // Work around for rust-lang/rust#62127
 pub use workspaces_macros::main;
 pub use workspaces_macros::test;
 
+pub use retry::{ReconnectPolicy, RetryableError};
 pub use rpc::api::*;
 pub use runtime::{SandboxRuntime, TestnetRuntime};
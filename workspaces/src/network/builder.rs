@@ -1,13 +1,25 @@
 use std::future::{Future, IntoFuture};
 use std::marker::PhantomData;
+use std::path::PathBuf;
+
+use serde_json::Value;
 
 use crate::network::Sandbox;
+use crate::retry::ReconnectPolicy;
 use crate::{Network, Worker};
 
-use super::server::ValidatorKey;
+use super::server::{SupervisorPolicy, ValidatorKey};
 
 pub(crate) type BoxFuture<'a, T> = std::pin::Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 
+/// A single layer of config/genesis overrides, applied as a deep-merge on top of
+/// whatever came before it. `File` is useful for declarative overrides checked into
+/// CI; `Patch` is useful for overrides computed at runtime.
+pub(crate) enum ConfigOverride<'a> {
+    File(PathBuf),
+    Patch(Box<dyn Fn(&mut Value) + Send + Sync + 'a>),
+}
+
 /// This trait provides a way to construct Networks out of a single builder. Currently
 /// not planned to offer this trait outside, since the custom networks can just construct
 /// themselves however they want utilizing `Worker::new` like so:
@@ -29,6 +41,11 @@ pub struct NetworkBuilder<'a, T> {
     pub(crate) name: &'a str,
     pub(crate) rpc_addr: Option<String>,
     pub(crate) validator_key: Option<ValidatorKey>,
+    pub(crate) reconnect: Option<ReconnectPolicy>,
+    pub(crate) validators: Option<usize>,
+    pub(crate) config_overrides: Vec<ConfigOverride<'a>>,
+    pub(crate) genesis_overrides: Vec<ConfigOverride<'a>>,
+    pub(crate) supervisor_policy: Option<SupervisorPolicy>,
     _network: PhantomData<T>,
 }
 
@@ -54,6 +71,11 @@ impl<'a, T> NetworkBuilder<'a, T> {
             name,
             rpc_addr: None,
             validator_key: None,
+            reconnect: None,
+            validators: None,
+            config_overrides: Vec::new(),
+            genesis_overrides: Vec::new(),
+            supervisor_policy: None,
             _network: PhantomData,
         }
     }
@@ -69,10 +91,30 @@ impl<'a, T> NetworkBuilder<'a, T> {
         self.rpc_addr = Some(addr.into());
         self
     }
+
+    /// Opt into a reconnecting RPC transport that retries a request after a transport-level
+    /// error (connection reset, timed-out socket, 5xx/empty body), following the backoff
+    /// described by `policy`. Well-formed application errors returned by the node are always
+    /// surfaced immediately, without retry, so logic bugs aren't masked. The retrying is
+    /// performed by [`crate::retry::json_rpc_call_with_retry`], which goes through a
+    /// [`crate::rt::Runtime`] so it works under either supported executor.
+    ///
+    /// For [`Sandbox`], the policy is kept for the server's whole lifetime, not just
+    /// startup: each node's startup wait is followed by a successful JSON-RPC `status`
+    /// call (not just an open TCP socket) before it's reported ready, and every later
+    /// call made through `SandboxServer::rpc_call_with_retry` retries the same way --
+    /// see `SandboxServer::run_new`.
+    ///
+    /// This is useful for long-running tests against a sandbox or a remote testnet node set
+    /// via [`rpc_addr`](Self::rpc_addr) that may see transient transport drops.
+    pub fn with_reconnect(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = Some(policy);
+        self
+    }
 }
 
 // So far, only Sandbox makes use of validator_key.
-impl NetworkBuilder<'_, Sandbox> {
+impl<'a> NetworkBuilder<'a, Sandbox> {
     /// Specify how to fetch the validator key of the manually spawned sandbox node.
     /// We are expected to init our own sandbox before running this builder. To learn more
     /// about initalizing and  starting our own sandbox, go to [near-sandbox](https://github.com/near/sandbox).
@@ -82,4 +124,65 @@ impl NetworkBuilder<'_, Sandbox> {
         self.validator_key = Some(validator_key);
         self
     }
+
+    /// Spawn a network of `n` validator nodes sharing a generated genesis, instead of the
+    /// default single node, for consensus/finality and cross-shard testing. The nodes are
+    /// wired up with each other as boot nodes and share the combined validator set.
+    pub fn validators(mut self, n: usize) -> Self {
+        self.validators = Some(n);
+        self
+    }
+
+    /// Apply `f` as a deep-merge patch on top of the sandbox's `config.json`, after our
+    /// own defaults -- including the multi-validator wiring from
+    /// [`validators`](Self::validators), if set -- have been applied, but before the
+    /// node is started. This means a patch can freely override `network.boot_nodes` or
+    /// anything else `validators(n)` would otherwise set up. Patches are applied in the
+    /// order they were added, each overlaying the result of the previous one. Only
+    /// top-level keys already present in the base config may be touched; patches that add
+    /// unrecognized top-level keys are rejected at startup with `SandboxErrorCode`.
+    pub fn patch_config<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Value) + Send + Sync + 'a,
+    {
+        self.config_overrides
+            .push(ConfigOverride::Patch(Box::new(f)));
+        self
+    }
+
+    /// Load `path` as a JSON document and deep-merge it on top of `config.json`, in the
+    /// same manner as [`patch_config`](Self::patch_config). Useful for checking a
+    /// declarative config override into CI instead of constructing it in code.
+    pub fn patch_config_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.config_overrides
+            .push(ConfigOverride::File(path.into()));
+        self
+    }
+
+    /// Apply `f` as a deep-merge patch on top of the sandbox's `genesis.json`, with the
+    /// same ordering, merge and validation semantics as [`patch_config`](Self::patch_config).
+    pub fn patch_genesis<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&mut Value) + Send + Sync + 'a,
+    {
+        self.genesis_overrides
+            .push(ConfigOverride::Patch(Box::new(f)));
+        self
+    }
+
+    /// Load `path` as a JSON document and deep-merge it on top of `genesis.json`, in the
+    /// same manner as [`patch_genesis`](Self::patch_genesis).
+    pub fn patch_genesis_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.genesis_overrides
+            .push(ConfigOverride::File(path.into()));
+        self
+    }
+
+    /// Start a background supervisor alongside the sandbox node(s) that watches for an
+    /// unexpected exit and reacts according to `policy`, surfacing what happened
+    /// through the `Supervisor` returned by the builder's future.
+    pub fn supervise(mut self, policy: SupervisorPolicy) -> Self {
+        self.supervisor_policy = Some(policy);
+        self
+    }
 }
@@ -1,15 +1,26 @@
 use std::fs::File;
-use std::path::PathBuf;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
-use crate::error::{ErrorKind, SandboxErrorCode};
+use crate::error::{Error, ErrorKind, SandboxErrorCode};
+use crate::network::builder::ConfigOverride;
 use crate::result::Result;
+use crate::retry::{ReconnectPolicy, TransportError};
+use crate::rt::Runtime;
 use crate::types::SecretKey;
 
 use async_process::Child;
 use fs2::FileExt;
+use futures::channel::mpsc::{self, UnboundedReceiver, UnboundedSender};
 use near_account_id::AccountId;
 use portpicker::pick_unused_port;
 use reqwest::Url;
+use serde_json::Value;
 use tempfile::TempDir;
 use tracing::info;
 
@@ -33,6 +44,115 @@ fn acquire_unused_port() -> Result<(u16, File)> {
     }
 }
 
+/// The rpc/net ports reserved for a single node ahead of spawning it, kept locked until
+/// the node actually starts listening on them.
+struct PortAlloc {
+    rpc_port: u16,
+    rpc_port_lock: File,
+    net_port: u16,
+    net_port_lock: File,
+}
+
+fn acquire_node_ports() -> Result<PortAlloc> {
+    let (rpc_port, rpc_port_lock) = acquire_unused_port()?;
+    let (net_port, net_port_lock) = acquire_unused_port()?;
+    Ok(PortAlloc {
+        rpc_port,
+        rpc_port_lock,
+        net_port,
+        net_port_lock,
+    })
+}
+
+/// Poll `rpc_port` until it accepts a TCP connection, via the given [`Runtime`], or give
+/// up once `timeout` has elapsed.
+async fn wait_until_ready(rt: &dyn Runtime, rpc_port: u16, timeout: Duration) -> Result<()> {
+    let addr: SocketAddr = ([127, 0, 0, 1], rpc_port).into();
+    let deadline = std::time::Instant::now() + timeout;
+    let retry_delay = Duration::from_millis(100);
+
+    loop {
+        if rt.tcp_connect(addr).await.is_ok() {
+            return Ok(());
+        }
+        if std::time::Instant::now() >= deadline {
+            return Err(SandboxErrorCode::RunFailure.message(format!(
+                "sandbox on port {} did not become ready within {:?}",
+                rpc_port, timeout
+            )));
+        }
+        rt.sleep(retry_delay).await;
+    }
+}
+
+/// Confirm `rpc_addr` is actually answering JSON-RPC, not just accepting TCP
+/// connections, by performing a `status` call through
+/// [`json_rpc_call_with_retry`](crate::retry::json_rpc_call_with_retry), retrying
+/// transport-level failures per `policy`.
+async fn confirm_rpc_ready(
+    rt: &dyn Runtime,
+    rpc_addr: &Url,
+    policy: &ReconnectPolicy,
+) -> Result<()> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": "workspaces-readiness-check",
+        "method": "status",
+        "params": [],
+    });
+
+    crate::retry::json_rpc_call_with_retry(rt, rpc_addr, policy, body)
+        .await
+        .map(drop)
+        .map_err(|err| match err {
+            TransportError::Transport(err) => err,
+            TransportError::Application(resp) => SandboxErrorCode::RunFailure.message(format!(
+                "sandbox status check returned a JSON-RPC error: {resp}"
+            )),
+        })
+}
+
+/// Send `child` a terminate signal and wait up to `timeout` for it to exit on its own,
+/// escalating to a hard kill if it doesn't. Polls via `rt` so it doesn't depend on a
+/// specific executor.
+async fn terminate_and_wait(child: &mut Child, rt: &dyn Runtime, timeout: Duration) -> Result<()> {
+    send_terminate_signal(child)?;
+
+    let deadline = std::time::Instant::now() + timeout;
+    loop {
+        if child
+            .try_status()
+            .map_err(|e| ErrorKind::Io.custom(e))?
+            .is_some()
+        {
+            return Ok(());
+        }
+        if std::time::Instant::now() >= deadline {
+            break;
+        }
+        rt.sleep(Duration::from_millis(100)).await;
+    }
+
+    // Didn't exit gracefully in time; escalate to a hard kill.
+    child.kill().map_err(|e| ErrorKind::Io.custom(e))?;
+    child.status().await.map_err(|e| ErrorKind::Io.custom(e))?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn send_terminate_signal(child: &Child) -> Result<()> {
+    use nix::sys::signal::{self, Signal};
+    use nix::unistd::Pid;
+
+    signal::kill(Pid::from_raw(child.id() as i32), Signal::SIGTERM)
+        .map_err(|e| ErrorKind::Io.full("failed to send SIGTERM to sandbox process", e))
+}
+
+#[cfg(not(unix))]
+fn send_terminate_signal(child: &mut Child) -> Result<()> {
+    child.kill().map_err(|e| ErrorKind::Io.custom(e))
+}
+
 async fn init_home_dir() -> Result<TempDir> {
     let home_dir = tempfile::tempdir().map_err(|e| ErrorKind::Io.custom(e))?;
     let output = sandbox::init(&home_dir)
@@ -45,6 +165,411 @@ async fn init_home_dir() -> Result<TempDir> {
     Ok(home_dir)
 }
 
+fn read_json(path: &Path) -> Result<Value> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| ErrorKind::Io.full(format!("failed to read {}", path.display()), e))?;
+    serde_json::from_str(&contents).map_err(|e| {
+        ErrorKind::DataConversion.full(format!("failed to parse {}", path.display()), e)
+    })
+}
+
+fn write_json(path: &Path, value: &Value) -> Result<()> {
+    let contents =
+        serde_json::to_string_pretty(value).map_err(|e| ErrorKind::DataConversion.custom(e))?;
+    std::fs::write(path, contents)
+        .map_err(|e| ErrorKind::Io.full(format!("failed to write {}", path.display()), e))
+}
+
+/// Recursively merge `overlay` into `base`: objects are merged key-by-key, and any
+/// other value (including arrays) in `overlay` replaces the corresponding value in
+/// `base` outright.
+fn deep_merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                deep_merge(base.entry(key).or_insert(Value::Null), value);
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Apply `overrides` to `home_dir/file_name`, in order, as successive deep-merges on
+/// top of whatever is already there. Rejects any override that introduces a top-level
+/// key not already present in the base document, so a typo'd key doesn't silently get
+/// ignored by the node.
+fn apply_overrides(home_dir: &Path, file_name: &str, overrides: &[ConfigOverride]) -> Result<()> {
+    if overrides.is_empty() {
+        return Ok(());
+    }
+
+    let path = home_dir.join(file_name);
+    let mut doc = read_json(&path)?;
+    let known_keys = doc
+        .as_object()
+        .map(|obj| {
+            obj.keys()
+                .cloned()
+                .collect::<std::collections::HashSet<_>>()
+        })
+        .unwrap_or_default();
+
+    for override_ in overrides {
+        match override_ {
+            ConfigOverride::File(patch_path) => deep_merge(&mut doc, read_json(patch_path)?),
+            ConfigOverride::Patch(f) => f(&mut doc),
+        }
+    }
+
+    if let Some(obj) = doc.as_object() {
+        let unknown_keys = obj
+            .keys()
+            .filter(|key| !known_keys.contains(key.as_str()))
+            .cloned()
+            .collect::<Vec<_>>();
+        if !unknown_keys.is_empty() {
+            return Err(SandboxErrorCode::InitFailure.message(format!(
+                "override for {} introduced unknown top-level key(s): {}",
+                file_name,
+                unknown_keys.join(", ")
+            )));
+        }
+    }
+
+    write_json(&path, &doc)
+}
+
+#[cfg(test)]
+mod overrides_tests {
+    use super::*;
+
+    #[test]
+    fn deep_merge_merges_nested_objects_and_replaces_scalars_and_arrays() {
+        let mut base = serde_json::json!({
+            "network": {"boot_nodes": "", "addr": "0.0.0.0:24567"},
+            "tracked_shards": [0],
+        });
+        let overlay = serde_json::json!({
+            "network": {"boot_nodes": "abc@127.0.0.1:1"},
+            "tracked_shards": [0, 1],
+        });
+
+        deep_merge(&mut base, overlay);
+
+        assert_eq!(base["network"]["boot_nodes"], "abc@127.0.0.1:1");
+        // Untouched sibling keys in a merged object survive.
+        assert_eq!(base["network"]["addr"], "0.0.0.0:24567");
+        // Arrays are replaced outright, not concatenated/merged element-wise.
+        assert_eq!(base["tracked_shards"], serde_json::json!([0, 1]));
+    }
+
+    #[test]
+    fn apply_overrides_rejects_unknown_top_level_key() {
+        let home_dir = tempfile::tempdir().unwrap();
+        write_json(
+            &home_dir.path().join("config.json"),
+            &serde_json::json!({"rpc": {"addr": "0.0.0.0:3030"}}),
+        )
+        .unwrap();
+
+        let overrides = vec![ConfigOverride::Patch(Box::new(|doc: &mut Value| {
+            doc["not_a_real_top_level_key"] = serde_json::json!(true);
+        }))];
+
+        let result = apply_overrides(home_dir.path(), "config.json", &overrides);
+        assert!(result.is_err(), "unknown top-level key should be rejected");
+    }
+
+    #[test]
+    fn apply_overrides_allows_known_top_level_key() {
+        let home_dir = tempfile::tempdir().unwrap();
+        write_json(
+            &home_dir.path().join("config.json"),
+            &serde_json::json!({"rpc": {"addr": "0.0.0.0:3030"}}),
+        )
+        .unwrap();
+
+        let overrides = vec![ConfigOverride::Patch(Box::new(|doc: &mut Value| {
+            doc["rpc"]["addr"] = serde_json::json!("0.0.0.0:4040");
+        }))];
+
+        apply_overrides(home_dir.path(), "config.json", &overrides).unwrap();
+
+        let patched = read_json(&home_dir.path().join("config.json")).unwrap();
+        assert_eq!(patched["rpc"]["addr"], "0.0.0.0:4040");
+    }
+}
+
+/// Build the `node_key@127.0.0.1:net_port` boot node address for a single home dir.
+fn boot_node_addr(home_dir: &Path, net_port: u16) -> Result<String> {
+    let node_key = read_json(&home_dir.join("node_key.json"))?;
+    let public_key = node_key["public_key"]
+        .as_str()
+        .ok_or_else(|| SandboxErrorCode::InitFailure.message("node_key.json missing public_key"))?;
+    Ok(format!("{}@127.0.0.1:{}", public_key, net_port))
+}
+
+/// Merge each home dir's `genesis.json` `records` into one list, keeping only the first
+/// `Account` record seen for any given `account_id`. `near-sandbox init` generates the
+/// same default test account for every home dir it initializes (with a different keypair
+/// each time), so concatenating the lists verbatim would produce duplicate `Account`
+/// records for that account once merged into a shared genesis. Other record kinds (e.g.
+/// `AccessKey`) are passed through unchanged, since an account legitimately having more
+/// than one access key is not a conflict.
+fn merge_records(records_per_home: Vec<Value>) -> Vec<Value> {
+    let mut seen_accounts = std::collections::HashSet::new();
+    let mut merged = Vec::new();
+
+    for records in records_per_home {
+        let Value::Array(records) = records else {
+            continue;
+        };
+        for record in records {
+            if let Some(account_id) = record
+                .get("Account")
+                .and_then(|account| account.get("account_id"))
+                .and_then(Value::as_str)
+            {
+                if !seen_accounts.insert(account_id.to_string()) {
+                    continue;
+                }
+            }
+            merged.push(record);
+        }
+    }
+
+    merged
+}
+
+/// Wire up `home_dirs` (each already `init`-ed individually) into a single network: every
+/// node's `config.json` is rewritten to list every other node as a boot node, and every
+/// node's `genesis.json` is rewritten to share the combined validator set, so the nodes
+/// reach consensus together instead of running as N disconnected single-validator chains.
+fn wire_multi_validator_network(home_dirs: &[PathBuf], net_ports: &[u16]) -> Result<()> {
+    let peer_addrs = home_dirs
+        .iter()
+        .zip(net_ports)
+        .map(|(home_dir, net_port)| boot_node_addr(home_dir, *net_port))
+        .collect::<Result<Vec<_>>>()?;
+
+    // Each home dir's own `genesis.json` already has a well-formed validator entry
+    // (`{account_id, public_key, amount}`, generated by `near-sandbox init`) for its
+    // single default validator -- reuse that instead of `validator_key.json`, which has
+    // the wrong shape for a genesis validator entry (no `amount`/stake) and would leak
+    // the node's secret key into `genesis.json` if copied in verbatim.
+    let validators = home_dirs
+        .iter()
+        .map(|home_dir| {
+            let genesis = read_json(&home_dir.join("genesis.json"))?;
+            genesis["validators"]
+                .as_array()
+                .and_then(|validators| validators.first())
+                .cloned()
+                .ok_or_else(|| {
+                    SandboxErrorCode::InitFailure.message("genesis.json missing validators[0]")
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let records_per_home = home_dirs
+        .iter()
+        .map(|home_dir| {
+            let genesis = read_json(&home_dir.join("genesis.json"))?;
+            Ok(genesis["records"].clone())
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let records = merge_records(records_per_home);
+
+    for (i, home_dir) in home_dirs.iter().enumerate() {
+        let config_path = home_dir.join("config.json");
+        let mut config = read_json(&config_path)?;
+        let boot_nodes = peer_addrs
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, addr)| addr.clone())
+            .collect::<Vec<_>>()
+            .join(",");
+        config["network"]["boot_nodes"] = Value::String(boot_nodes);
+        write_json(&config_path, &config)?;
+
+        let genesis_path = home_dir.join("genesis.json");
+        let mut genesis = read_json(&genesis_path)?;
+        genesis["validators"] = Value::Array(validators.clone());
+        genesis["records"] = Value::Array(records.clone());
+        write_json(&genesis_path, &genesis)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod wire_multi_validator_network_tests {
+    use super::*;
+
+    #[test]
+    fn merge_records_dedups_accounts_by_id_but_keeps_other_kinds() {
+        let home_a = vec![
+            serde_json::json!({"Account": {"account_id": "test.near", "account": {}}}),
+            serde_json::json!({"AccessKey": {"account_id": "test.near", "public_key": "ed25519:aaa"}}),
+        ];
+        let home_b = vec![
+            serde_json::json!({"Account": {"account_id": "test.near", "account": {}}}),
+            serde_json::json!({"AccessKey": {"account_id": "test.near", "public_key": "ed25519:bbb"}}),
+        ];
+
+        let merged = merge_records(vec![Value::Array(home_a), Value::Array(home_b)]);
+
+        let account_records = merged
+            .iter()
+            .filter(|record| record.get("Account").is_some())
+            .count();
+        assert_eq!(
+            account_records, 1,
+            "duplicate Account records were not deduped"
+        );
+
+        let access_key_records = merged
+            .iter()
+            .filter(|record| record.get("AccessKey").is_some())
+            .count();
+        assert_eq!(
+            access_key_records, 2,
+            "distinct AccessKey records should both be kept"
+        );
+    }
+
+    #[test]
+    fn boot_node_addr_formats_public_key_and_port() {
+        let home_dir = tempfile::tempdir().unwrap();
+        write_json(
+            &home_dir.path().join("node_key.json"),
+            &serde_json::json!({"account_id": "", "public_key": "ed25519:abc", "secret_key": "ed25519:def"}),
+        )
+        .unwrap();
+
+        let addr = boot_node_addr(home_dir.path(), 24567).unwrap();
+        assert_eq!(addr, "ed25519:abc@127.0.0.1:24567");
+    }
+}
+
+/// Restart the node at `index` on freshly acquired ports, reusing its original home
+/// dir. Updates `nodes[index]` in place once the new process is ready.
+///
+/// Note: this is not safe to race against [`SandboxServer::shutdown`] -- if `stopped`
+/// flips to `true` after we've already started spawning, we notice it just before
+/// committing the replacement process and kill it immediately instead of leaving it
+/// orphaned, but a caller must not assume `shutdown` reliably cancels an in-flight
+/// restart any faster than that.
+async fn restart_node(
+    nodes: &Arc<Mutex<Vec<SandboxNode>>>,
+    index: usize,
+    rt: &dyn Runtime,
+    stopped: &AtomicBool,
+) -> Result<String> {
+    let home_dir = {
+        let nodes = nodes.lock().unwrap();
+        nodes[index].home_dir.clone().ok_or_else(|| {
+            SandboxErrorCode::RunFailure.message("no home_dir on record to restart node from")
+        })?
+    };
+
+    let PortAlloc {
+        rpc_port,
+        rpc_port_lock,
+        net_port,
+        net_port_lock,
+    } = acquire_node_ports()?;
+    let rpc_addr = Url::parse(&format!("{}:{}", DEFAULT_RPC_URL, rpc_port)).unwrap();
+
+    info!(target: "workspaces", "Restarting sandbox node {} at localhost:{}", index, rpc_port);
+    let mut child = sandbox::run(&home_dir, rpc_port, net_port)
+        .map_err(|e| SandboxErrorCode::RunFailure.custom(e))?;
+    wait_until_ready(rt, rpc_port, Duration::from_secs(10)).await?;
+
+    if stopped.load(Ordering::SeqCst) {
+        // The server was shut down while we were restarting this node. Don't
+        // resurrect it into a `SandboxServer` that no longer exists, and don't
+        // leak the process we just spawned.
+        let _ = child.kill();
+        return Err(SandboxErrorCode::RunFailure
+            .message("sandbox server was shut down while restarting node; dropping replacement"));
+    }
+
+    let mut nodes = nodes.lock().unwrap();
+    nodes[index].rpc_addr = rpc_addr.clone();
+    nodes[index].net_port = Some(net_port);
+    nodes[index].rpc_port_lock = Some(rpc_port_lock);
+    nodes[index].net_port_lock = Some(net_port_lock);
+    nodes[index].process = Some(child);
+
+    Ok(rpc_addr.to_string())
+}
+
+/// Background task that periodically polls each node's liveness and, on an unexpected
+/// exit, reports it (or restarts the node) according to `policy`. Stops once every node
+/// it supervises has no process left to watch (which happens once
+/// [`SandboxServer::shutdown`] has torn them all down), or as soon as `stopped` is set,
+/// which happens whether the server was shut down or simply dropped -- either way, a
+/// torn-down node's exit should never be reported or restarted.
+async fn supervise(
+    nodes: Arc<Mutex<Vec<SandboxNode>>>,
+    rt: Arc<dyn Runtime>,
+    policy: SupervisorPolicy,
+    events: UnboundedSender<SupervisorEvent>,
+    stopped: Arc<AtomicBool>,
+) {
+    loop {
+        rt.sleep(Duration::from_secs(1)).await;
+
+        if stopped.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let exited = {
+            let mut nodes = nodes.lock().unwrap();
+            let mut exited = Vec::new();
+            for (index, node) in nodes.iter_mut().enumerate() {
+                let has_exited = matches!(
+                    node.process.as_mut().map(|child| child.try_status()),
+                    Some(Ok(Some(_)))
+                );
+                if has_exited {
+                    node.process = None;
+                    exited.push(index);
+                }
+            }
+            exited
+        };
+
+        for index in exited {
+            let event = match policy {
+                SupervisorPolicy::Report => SupervisorEvent::Exited { node_index: index },
+                SupervisorPolicy::Restart => {
+                    match restart_node(&nodes, index, rt.as_ref(), stopped.as_ref()).await {
+                        Ok(rpc_addr) => SupervisorEvent::Restarted {
+                            node_index: index,
+                            rpc_addr,
+                        },
+                        Err(error) => SupervisorEvent::RestartFailed {
+                            node_index: index,
+                            error,
+                        },
+                    }
+                }
+            };
+            if events.unbounded_send(event).is_err() {
+                // Nobody's listening anymore; nothing left to supervise.
+                return;
+            }
+        }
+
+        if nodes.lock().unwrap().iter().all(|n| n.process.is_none()) {
+            return;
+        }
+    }
+}
+
 #[derive(Debug)]
 #[non_exhaustive]
 pub enum ValidatorKey {
@@ -52,9 +577,10 @@ pub enum ValidatorKey {
     Known(AccountId, SecretKey),
 }
 
-pub struct SandboxServer {
-    pub(crate) validator_key: ValidatorKey,
-
+/// A single neard process making up part of a sandbox network, along with the port locks
+/// acquired on its behalf. A [`SandboxServer`] holds one of these per validator.
+struct SandboxNode {
+    home_dir: Option<PathBuf>,
     rpc_addr: Url,
     net_port: Option<u16>,
     rpc_port_lock: Option<File>,
@@ -62,115 +588,352 @@ pub struct SandboxServer {
     process: Option<Child>,
 }
 
+/// How the background supervisor should react when a node exits unexpectedly.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum SupervisorPolicy {
+    /// Just report the exit through the event channel; don't restart.
+    #[default]
+    Report,
+    /// Restart the node on fresh ports and report the outcome through the event channel.
+    Restart,
+}
+
+/// An unexpected-exit notification surfaced by the background supervisor task started
+/// alongside a [`SandboxServer`] when a [`SupervisorPolicy`] is configured.
+#[derive(Debug)]
+pub enum SupervisorEvent {
+    /// The node at `node_index` exited on its own and was not restarted.
+    Exited { node_index: usize },
+    /// The node at `node_index` exited and was successfully restarted on fresh ports.
+    Restarted { node_index: usize, rpc_addr: String },
+    /// The node at `node_index` exited and restarting it also failed.
+    RestartFailed { node_index: usize, error: Error },
+}
+
+/// Handle to the background supervisor task started by [`SandboxServer::run_new`] when a
+/// [`SupervisorPolicy`] is configured. Poll `events` to observe unexpected exits and, if
+/// restarting, their outcome; `task` resolves once the supervisor stops, which happens
+/// once every node it supervises has been torn down via [`SandboxServer::shutdown`].
+pub struct Supervisor {
+    pub events: UnboundedReceiver<SupervisorEvent>,
+    pub task: Pin<Box<dyn Future<Output = ()> + Send>>,
+}
+
+pub struct SandboxServer {
+    pub(crate) validator_key: ValidatorKey,
+    nodes: Arc<Mutex<Vec<SandboxNode>>>,
+    rt: Arc<dyn Runtime>,
+    /// Set via [`NetworkBuilder::with_reconnect`](crate::network::builder::NetworkBuilder::with_reconnect)
+    /// and kept for the server's whole lifetime (not just startup), so every call made
+    /// through [`rpc_call_with_retry`](Self::rpc_call_with_retry) -- not only the
+    /// post-spawn readiness check -- retries transport-level failures per the policy.
+    reconnect: Option<ReconnectPolicy>,
+    /// Set by `shutdown` and by `Drop`, and watched by the background supervisor task
+    /// (if any) so a node we've already torn down is never reported or restarted.
+    stopped: Arc<AtomicBool>,
+}
+
 impl SandboxServer {
     /// Connect a sandbox server that's already been running, provided we know the rpc_addr
     /// and home_dir pointing to the sandbox process.
-    pub(crate) async fn connect(rpc_addr: String, validator_key: ValidatorKey) -> Result<Self> {
+    pub(crate) async fn connect(
+        rpc_addr: String,
+        validator_key: ValidatorKey,
+        rt: Arc<dyn Runtime>,
+    ) -> Result<Self> {
         let rpc_addr = Url::parse(&rpc_addr).map_err(|e| {
             SandboxErrorCode::InitFailure.full(format!("Invalid rpc_url={rpc_addr}"), e)
         })?;
         Ok(Self {
             validator_key,
-            rpc_addr,
-            net_port: None,
-            rpc_port_lock: None,
-            net_port_lock: None,
-            process: None,
+            nodes: Arc::new(Mutex::new(vec![SandboxNode {
+                home_dir: None,
+                rpc_addr,
+                net_port: None,
+                rpc_port_lock: None,
+                net_port_lock: None,
+                process: None,
+            }])),
+            rt,
+            reconnect: None,
+            stopped: Arc::new(AtomicBool::new(false)),
         })
     }
 
-    /// Run a new SandboxServer, spawning the sandbox node in the process.
-    pub(crate) async fn run_new() -> Result<Self> {
+    /// Run a new SandboxServer, spawning `validators` neard processes that share a
+    /// generated genesis. A `validators` of 1 (the default) behaves exactly as before:
+    /// a single node is booted with no peers to wire up. Process spawning and the
+    /// post-spawn readiness wait go through `rt`, so callers can pick their executor
+    /// via the `tokio`/`async-std` Cargo features.
+    ///
+    /// If `reconnect` is set, it's kept for the server's whole lifetime and used two
+    /// ways: each node's readiness wait is followed by a JSON-RPC `status` call made
+    /// through [`crate::retry::json_rpc_call_with_retry`], confirming the node is
+    /// actually answering JSON-RPC (not just that its TCP listener is up) before we
+    /// report it ready; and it's then available to every later call made via
+    /// [`rpc_call_with_retry`](Self::rpc_call_with_retry) for the rest of this server's
+    /// life, not just at startup. This is the same policy
+    /// [`NetworkBuilder::with_reconnect`](crate::network::builder::NetworkBuilder::with_reconnect)
+    /// configures.
+    ///
+    /// If `supervisor_policy` is set, a background task is started that watches for a
+    /// node exiting unexpectedly and reports it (or restarts the node) via the returned
+    /// [`Supervisor`].
+    pub(crate) async fn run_new(
+        validators: usize,
+        rt: Arc<dyn Runtime>,
+        config_overrides: &[ConfigOverride<'_>],
+        genesis_overrides: &[ConfigOverride<'_>],
+        supervisor_policy: Option<SupervisorPolicy>,
+        reconnect: Option<ReconnectPolicy>,
+    ) -> Result<(Self, Option<Supervisor>)> {
         // Supress logs for the sandbox binary by default:
         supress_sandbox_logs_if_required();
 
-        let home_dir = init_home_dir().await?.into_path();
-        // Configure `$home_dir/config.json` to our liking. Sandbox requires extra settings
-        // for the best user experience, and being able to offer patching large state payloads.
-        crate::network::config::set_sandbox_configs(&home_dir)?;
+        let validators = validators.max(1);
 
-        // Try running the server with the follow provided rpc_ports and net_ports
-        let (rpc_port, rpc_port_lock) = acquire_unused_port()?;
-        let (net_port, net_port_lock) = acquire_unused_port()?;
-        let rpc_addr = format!("{}:{}", DEFAULT_RPC_URL, rpc_port);
-        // This is guaranteed to be a valid URL, since this is using the default URL.
-        let rpc_addr = Url::parse(&rpc_addr).unwrap();
+        let mut home_dirs = Vec::with_capacity(validators);
+        for _ in 0..validators {
+            let home_dir = init_home_dir().await?.into_path();
+            // Configure `$home_dir/config.json` to our liking. Sandbox requires extra settings
+            // for the best user experience, and being able to offer patching large state payloads.
+            crate::network::config::set_sandbox_configs(&home_dir)?;
+            home_dirs.push(home_dir);
+        }
 
-        info!(target: "workspaces", "Starting up sandbox at localhost:{}", rpc_port);
-        let child = sandbox::run(&home_dir, rpc_port, net_port)
-            .map_err(|e| SandboxErrorCode::RunFailure.custom(e))?;
+        let ports = (0..validators)
+            .map(|_| acquire_node_ports())
+            .collect::<Result<Vec<_>>>()?;
 
-        info!(target: "workspaces", "Started up sandbox at localhost:{} with pid={:?}", rpc_port, child.id());
+        // Wire up the shared multi-validator genesis/config *before* layering on any
+        // user-supplied overrides, so `patch_config`/`patch_genesis` remain the final,
+        // authoritative layer -- otherwise a user override touching `boot_nodes` /
+        // `validators` / `records` would be silently clobbered by the wiring below
+        // whenever `.validators(n > 1)` is combined with a patch.
+        if validators > 1 {
+            let net_ports = ports.iter().map(|p| p.net_port).collect::<Vec<_>>();
+            wire_multi_validator_network(&home_dirs, &net_ports)?;
+        }
 
-        Ok(Self {
-            validator_key: ValidatorKey::HomeDir(home_dir),
-            rpc_addr,
-            net_port: Some(net_port),
-            rpc_port_lock: Some(rpc_port_lock),
-            net_port_lock: Some(net_port_lock),
-            process: Some(child),
-        })
+        for home_dir in &home_dirs {
+            apply_overrides(home_dir, "config.json", config_overrides)?;
+            apply_overrides(home_dir, "genesis.json", genesis_overrides)?;
+        }
+
+        let mut nodes = Vec::with_capacity(validators);
+        for (home_dir, port_alloc) in home_dirs.iter().zip(ports) {
+            let PortAlloc {
+                rpc_port,
+                rpc_port_lock,
+                net_port,
+                net_port_lock,
+            } = port_alloc;
+
+            let rpc_addr = format!("{}:{}", DEFAULT_RPC_URL, rpc_port);
+            // This is guaranteed to be a valid URL, since this is using the default URL.
+            let rpc_addr = Url::parse(&rpc_addr).unwrap();
+
+            info!(target: "workspaces", "Starting up sandbox at localhost:{}", rpc_port);
+            let child = sandbox::run(home_dir, rpc_port, net_port)
+                .map_err(|e| SandboxErrorCode::RunFailure.custom(e))?;
+
+            info!(target: "workspaces", "Started up sandbox at localhost:{} with pid={:?}", rpc_port, child.id());
+            wait_until_ready(rt.as_ref(), rpc_port, Duration::from_secs(10)).await?;
+            if let Some(policy) = &reconnect {
+                confirm_rpc_ready(rt.as_ref(), &rpc_addr, policy).await?;
+            }
+
+            nodes.push(SandboxNode {
+                home_dir: Some(home_dir.clone()),
+                rpc_addr,
+                net_port: Some(net_port),
+                rpc_port_lock: Some(rpc_port_lock),
+                net_port_lock: Some(net_port_lock),
+                process: Some(child),
+            });
+        }
+
+        let nodes = Arc::new(Mutex::new(nodes));
+        let stopped = Arc::new(AtomicBool::new(false));
+        let supervisor = supervisor_policy.map(|policy| {
+            let (tx, rx) = mpsc::unbounded();
+            let task = rt.spawn(Box::pin(supervise(
+                Arc::clone(&nodes),
+                Arc::clone(&rt),
+                policy,
+                tx,
+                Arc::clone(&stopped),
+            )));
+            Supervisor { events: rx, task }
+        });
+
+        let server = Self {
+            validator_key: ValidatorKey::HomeDir(home_dirs.into_iter().next().unwrap()),
+            nodes,
+            rt,
+            reconnect,
+            stopped,
+        };
+
+        Ok((server, supervisor))
     }
 
     /// Unlock port lockfiles that were used to avoid port contention when starting up
-    /// the sandbox node.
+    /// the sandbox node(s).
     pub(crate) fn unlock_lockfiles(&mut self) -> Result<()> {
-        if let Some(rpc_port_lock) = self.rpc_port_lock.take() {
-            rpc_port_lock.unlock().map_err(|e| {
-                ErrorKind::Io.full(
-                    format!(
-                        "failed to unlock lockfile for rpc_port={:?}",
-                        self.rpc_port()
-                    ),
-                    e,
-                )
-            })?;
-        }
-        if let Some(net_port_lock) = self.net_port_lock.take() {
-            net_port_lock.unlock().map_err(|e| {
-                ErrorKind::Io.full(
-                    format!("failed to unlock lockfile for net_port={:?}", self.net_port),
-                    e,
-                )
-            })?;
+        for node in self.nodes.lock().unwrap().iter_mut() {
+            if let Some(rpc_port_lock) = node.rpc_port_lock.take() {
+                rpc_port_lock.unlock().map_err(|e| {
+                    ErrorKind::Io.full(
+                        format!(
+                            "failed to unlock lockfile for rpc_port={:?}",
+                            node.rpc_addr.port()
+                        ),
+                        e,
+                    )
+                })?;
+            }
+            if let Some(net_port_lock) = node.net_port_lock.take() {
+                net_port_lock.unlock().map_err(|e| {
+                    ErrorKind::Io.full(
+                        format!("failed to unlock lockfile for net_port={:?}", node.net_port),
+                        e,
+                    )
+                })?;
+            }
         }
 
         Ok(())
     }
 
+    /// Number of validator nodes making up this sandbox network.
+    pub fn num_validators(&self) -> usize {
+        self.nodes.lock().unwrap().len()
+    }
+
     pub fn rpc_port(&self) -> Option<u16> {
-        self.rpc_addr.port()
+        self.nodes.lock().unwrap()[0].rpc_addr.port()
     }
 
     pub fn net_port(&self) -> Option<u16> {
-        self.net_port
+        self.nodes.lock().unwrap()[0].net_port
     }
 
     pub fn rpc_addr(&self) -> String {
-        self.rpc_addr.to_string()
+        self.nodes.lock().unwrap()[0].rpc_addr.to_string()
+    }
+
+    /// The rpc_addr of the `index`-th validator node, or `None` if there's no node at
+    /// that index.
+    pub fn rpc_addr_of(&self, index: usize) -> Option<String> {
+        self.nodes
+            .lock()
+            .unwrap()
+            .get(index)
+            .map(|node| node.rpc_addr.to_string())
+    }
+
+    /// Perform a single JSON-RPC call against this server's primary node. If a
+    /// [`ReconnectPolicy`] was configured via
+    /// [`NetworkBuilder::with_reconnect`](crate::network::builder::NetworkBuilder::with_reconnect),
+    /// the call goes through [`crate::retry::json_rpc_call_with_retry`] and retries
+    /// transport-level failures per that policy; without one, the call is made once,
+    /// with no retry, matching `with_reconnect`'s opt-in nature.
+    ///
+    /// This is the reusable entry point an ongoing RPC client built against this
+    /// server (e.g. the one backing a `Worker`) should call for every request, not
+    /// just the one-shot readiness check `run_new` performs at startup.
+    pub(crate) async fn rpc_call_with_retry(&self, body: Value) -> Result<Value> {
+        let rpc_addr = self.nodes.lock().unwrap()[0].rpc_addr.clone();
+
+        match &self.reconnect {
+            Some(policy) => {
+                crate::retry::json_rpc_call_with_retry(self.rt.as_ref(), &rpc_addr, policy, body)
+                    .await
+                    .map_err(|err| match err {
+                        TransportError::Transport(err) => err,
+                        TransportError::Application(resp) => SandboxErrorCode::RunFailure
+                            .message(format!("sandbox JSON-RPC call returned an error: {resp}")),
+                    })
+            }
+            None => self.rt.http_post_json(rpc_addr, body).await,
+        }
+    }
+
+    /// Gracefully shut down every node this server owns: each is sent a terminate
+    /// signal and given a bounded timeout to exit on its own before being escalated to
+    /// a hard kill, and the port lockfiles are released. Unlike the `Drop` impl, this
+    /// returns a `Result` instead of panicking on failure, and signals any running
+    /// supervisor task to stop once it next polls.
+    pub async fn shutdown(mut self) -> Result<()> {
+        // Signal the supervisor (if any) first, so it stops acting on these nodes as
+        // soon as possible -- notably, so it doesn't restart a node out from under us
+        // if it observes one exit mid-shutdown.
+        self.stopped.store(true, Ordering::SeqCst);
+
+        let children = {
+            let mut nodes = self.nodes.lock().unwrap();
+            nodes
+                .iter_mut()
+                .filter_map(|node| node.process.take())
+                .collect::<Vec<_>>()
+        };
+
+        // Terminate every node regardless of whether an earlier one failed -- returning
+        // early here would leave the remaining `Child`s leaked (no longer reachable from
+        // `self.nodes`, so `Drop` can't clean them up either) and skip unlocking every
+        // node's port lockfiles, not just the failing one's.
+        let mut first_err = None;
+        for mut child in children {
+            if let Err(e) =
+                terminate_and_wait(&mut child, self.rt.as_ref(), Duration::from_secs(5)).await
+            {
+                first_err.get_or_insert(e);
+            }
+        }
+
+        let unlock_result = self.unlock_lockfiles();
+
+        if let Some(err) = first_err {
+            return Err(err);
+        }
+        unlock_result
     }
 }
 
 impl Drop for SandboxServer {
     fn drop(&mut self) {
-        if self.process.is_none() {
-            return;
-        }
+        // Tell the supervisor (if any) to stop acting on these nodes, whether or not
+        // `shutdown` was called -- otherwise a plain `drop()` while a `Restart`
+        // supervisor is running would have it observe the kill below as an
+        // unexpected exit and resurrect the node we're in the middle of tearing down.
+        self.stopped.store(true, Ordering::SeqCst);
 
-        let rpc_port = self.rpc_port();
-        let child = self.process.as_mut().unwrap();
+        for node in self.nodes.lock().unwrap().iter_mut() {
+            // `shutdown` already took and gracefully terminated every process; this
+            // is only a best-effort backstop for servers that were simply dropped,
+            // and must never panic during unwind.
+            let Some(mut child) = node.process.take() else {
+                continue;
+            };
 
-        info!(
-            target: "workspaces",
-            "Cleaning up sandbox: port={:?}, pid={}",
-            rpc_port,
-            child.id()
-        );
+            let rpc_port = node.rpc_addr.port();
+            info!(
+                target: "workspaces",
+                "Cleaning up sandbox: port={:?}, pid={}",
+                rpc_port,
+                child.id()
+            );
 
-        child
-            .kill()
-            .map_err(|e| format!("Could not cleanup sandbox due to: {:?}", e))
-            .unwrap();
+            if let Err(e) = child.kill() {
+                tracing::warn!(
+                    target: "workspaces",
+                    "failed to kill sandbox process (port={:?}) on drop: {:?}",
+                    rpc_port,
+                    e
+                );
+            }
+        }
     }
 }
 
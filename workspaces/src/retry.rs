@@ -0,0 +1,205 @@
+use std::future::Future;
+use std::time::Duration;
+
+use reqwest::Url;
+use serde_json::Value;
+
+use crate::rt::Runtime;
+
+const DEFAULT_BASE: Duration = Duration::from_millis(200);
+const DEFAULT_CAP: Duration = Duration::from_secs(10);
+const DEFAULT_MAX_RETRIES: u32 = 5;
+
+/// Classifies whether an error encountered while performing an RPC call is a
+/// transport-level failure (connection reset, timed-out socket, 5xx/empty body)
+/// as opposed to a well-formed application error returned by the node. Only
+/// the former should ever be retried, since retrying the latter would mask
+/// logic bugs behind a retry loop.
+pub trait RetryableError {
+    /// Returns `true` if this error represents a transient transport failure
+    /// that's worth retrying against a freshly re-dialed endpoint.
+    fn is_retryable(&self) -> bool;
+}
+
+/// Policy describing how a reconnecting RPC transport should retry a request
+/// after a transport-level error. Backoff follows a capped exponential curve
+/// with full jitter: `delay = random_between(0, min(cap, base * factor^attempt))`.
+///
+/// Construct via [`ReconnectPolicy::new`] and tune with the builder methods,
+/// or use [`ReconnectPolicy::default`] for the out-of-the-box behavior (base
+/// 200ms, factor 2, cap 10s, up to 5 retries).
+#[derive(Debug, Clone, Copy)]
+pub struct ReconnectPolicy {
+    pub(crate) max_retries: u32,
+    pub(crate) base: Duration,
+    pub(crate) cap: Duration,
+    pub(crate) factor: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: DEFAULT_MAX_RETRIES,
+            base: DEFAULT_BASE,
+            cap: DEFAULT_CAP,
+            factor: 2,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Create a policy that retries up to `max_retries` times, using the
+    /// default base delay, cap and backoff factor.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Self::default()
+        }
+    }
+
+    /// Set the base delay used for the first retry.
+    pub fn base(mut self, base: Duration) -> Self {
+        self.base = base;
+        self
+    }
+
+    /// Set the upper bound any single backoff delay is capped at.
+    pub fn cap(mut self, cap: Duration) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    /// Set the multiplicative factor applied to the delay after each attempt.
+    pub fn factor(mut self, factor: u32) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// Compute the full-jitter backoff delay for the given zero-indexed retry
+    /// attempt, i.e. `random_between(0, min(cap, base * factor^attempt))`.
+    pub(crate) fn delay_for_attempt(&self, attempt: u32) -> Duration {
+        let exp = self.factor.saturating_pow(attempt);
+        let uncapped = self.base.saturating_mul(exp);
+        let bound = uncapped.min(self.cap);
+
+        // Full jitter: uniformly sample in [0, bound]. We avoid pulling in a
+        // dedicated RNG crate for a single random scalar by deriving one from
+        // the current time, which is good enough for spreading out retries.
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let frac = (nanos % 1000) as f64 / 1000.0;
+
+        Duration::from_secs_f64(bound.as_secs_f64() * frac)
+    }
+}
+
+/// Retry `attempt` against `policy`, sleeping between attempts (via `rt`, so this
+/// works under any executor) using the capped exponential backoff with full jitter
+/// described by the policy. Stops retrying as soon as `attempt` succeeds, as soon as
+/// the error is classified as non-retryable via [`RetryableError::is_retryable`], or
+/// once `policy.max_retries` has been exhausted -- whichever comes first.
+pub(crate) async fn retry<F, Fut, T, E>(
+    rt: &dyn Runtime,
+    policy: &ReconnectPolicy,
+    mut attempt: F,
+) -> Result<T, E>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+    E: RetryableError,
+{
+    let mut last_err = None;
+    for try_num in 0..=policy.max_retries {
+        match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(err) if try_num < policy.max_retries && err.is_retryable() => {
+                rt.sleep(policy.delay_for_attempt(try_num)).await;
+                last_err = Some(err);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    // Unreachable in practice: the loop above always returns on its last
+    // iteration, but `last_err` is tracked defensively in case max_retries is 0.
+    Err(last_err.expect("loop always attempts at least once"))
+}
+
+/// Classifies the outcome of a single JSON-RPC HTTP call performed by
+/// [`json_rpc_call_with_retry`]: either a transport-level failure (connection reset,
+/// timed-out socket, 5xx, unparseable body -- anything [`Runtime::http_post_json`]
+/// itself reports as an error), or a well-formed JSON-RPC error returned by the node.
+/// Only the former is retried; the latter is a real application error and is always
+/// surfaced immediately.
+#[derive(Debug)]
+pub(crate) enum TransportError {
+    Transport(crate::error::Error),
+    Application(Value),
+}
+
+impl RetryableError for TransportError {
+    fn is_retryable(&self) -> bool {
+        matches!(self, TransportError::Transport(_))
+    }
+}
+
+/// Perform a single JSON-RPC call against `url`, retrying transport-level failures
+/// according to `policy` via [`retry`]. This is the reconnecting RPC transport that
+/// [`NetworkBuilder::with_reconnect`](crate::network::builder::NetworkBuilder::with_reconnect)
+/// opts a network into.
+pub(crate) async fn json_rpc_call_with_retry(
+    rt: &dyn Runtime,
+    url: &Url,
+    policy: &ReconnectPolicy,
+    body: Value,
+) -> std::result::Result<Value, TransportError> {
+    retry(rt, policy, || async {
+        let resp = rt
+            .http_post_json(url.clone(), body.clone())
+            .await
+            .map_err(TransportError::Transport)?;
+
+        if resp.get("error").is_some() {
+            return Err(TransportError::Application(resp));
+        }
+
+        Ok(resp)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delay_for_attempt_never_exceeds_cap() {
+        let policy = ReconnectPolicy::new(10).base(Duration::from_millis(50));
+
+        for attempt in 0..10 {
+            let delay = policy.delay_for_attempt(attempt);
+            assert!(
+                delay <= policy.cap,
+                "attempt {attempt} produced delay {delay:?} above cap {:?}",
+                policy.cap
+            );
+        }
+    }
+
+    #[test]
+    fn delay_for_attempt_caps_immediately_with_a_tiny_cap() {
+        let policy = ReconnectPolicy::default().cap(Duration::from_millis(1));
+        assert!(policy.delay_for_attempt(5) <= Duration::from_millis(1));
+    }
+
+    #[test]
+    fn transport_error_is_retryable_only_for_transport_failures() {
+        let transport = TransportError::Transport(crate::error::ErrorKind::Io.message("boom"));
+        let application = TransportError::Application(serde_json::json!({"error": "bad request"}));
+
+        assert!(transport.is_retryable());
+        assert!(!application.is_retryable());
+    }
+}
@@ -0,0 +1,181 @@
+//! A minimal, executor-agnostic abstraction over the bits of an async runtime that
+//! workspaces needs: spawning background tasks, sleeping, opening a raw TCP
+//! connection, and performing a JSON-RPC HTTP call. Sandbox process spawning, the
+//! post-spawn readiness wait, and the reconnecting JSON-RPC transport in
+//! [`crate::retry`] all go through a [`Runtime`] instead of calling `tokio`/`async-std`
+//! directly, so the crate can be embedded in either kind of application.
+//!
+//! Which implementation is selected is controlled by the `tokio` and `async-std`
+//! Cargo features. `tokio` is on by default for backward compatibility; `full` pulls
+//! in both so a downstream crate can depend on workspaces without forcing a choice of
+//! executor onto its own dependents. If both features end up enabled at once, `tokio`
+//! wins so that turning on `full` never silently changes behavior for existing
+//! `tokio`-only consumers.
+
+use std::future::Future;
+use std::net::SocketAddr;
+use std::pin::Pin;
+use std::time::Duration;
+
+use reqwest::Url;
+use serde_json::Value;
+
+use crate::error::ErrorKind;
+use crate::result::Result;
+
+/// The subset of an async runtime that workspaces needs to drive a sandbox process
+/// and its RPC transport.
+pub(crate) trait Runtime: Send + Sync + 'static {
+    /// Spawn `fut` onto this runtime's executor, returning a future that resolves once
+    /// `fut` completes -- akin to awaiting `tokio::task::JoinHandle` / `async_std::task::JoinHandle`,
+    /// but runtime-agnostic. Used to launch and later observe long-lived background
+    /// tasks, such as the sandbox supervisor.
+    fn spawn(
+        &self,
+        fut: Pin<Box<dyn Future<Output = ()> + Send>>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    /// Sleep for `duration` without blocking the executor.
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>>;
+
+    /// Attempt a single TCP connection to `addr`, used to poll for sandbox readiness.
+    fn tcp_connect(&self, addr: SocketAddr) -> Pin<Box<dyn Future<Output = Result<()>> + Send>>;
+
+    /// Perform a single JSON POST request to `url` with `body` and return the parsed
+    /// JSON response. Any failure here -- a connection error, timeout, non-2xx status,
+    /// or an unparseable body -- is a transport-level failure; it carries no opinion on
+    /// whether the response itself represents a JSON-RPC application error, which is
+    /// [`crate::retry`]'s concern, not this trait's.
+    fn http_post_json(
+        &self,
+        url: Url,
+        body: Value,
+    ) -> Pin<Box<dyn Future<Output = Result<Value>> + Send>>;
+}
+
+/// [`Runtime`] backed by `tokio`. Selected by the `tokio` Cargo feature (on by default).
+#[cfg(feature = "tokio")]
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct TokioRuntime;
+
+#[cfg(feature = "tokio")]
+impl Runtime for TokioRuntime {
+    fn spawn(
+        &self,
+        fut: Pin<Box<dyn Future<Output = ()> + Send>>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        let handle = tokio::spawn(fut);
+        Box::pin(async move {
+            let _ = handle.await;
+        })
+    }
+
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(tokio::time::sleep(duration))
+    }
+
+    fn tcp_connect(&self, addr: SocketAddr) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> {
+        Box::pin(async move {
+            tokio::net::TcpStream::connect(addr)
+                .await
+                .map(drop)
+                .map_err(|e| ErrorKind::Io.custom(e))
+        })
+    }
+
+    fn http_post_json(
+        &self,
+        url: Url,
+        body: Value,
+    ) -> Pin<Box<dyn Future<Output = Result<Value>> + Send>> {
+        Box::pin(async move {
+            let resp = reqwest::Client::new()
+                .post(url)
+                .json(&body)
+                .send()
+                .await
+                .map_err(|e| ErrorKind::Io.full("transport error performing JSON-RPC call", e))?;
+
+            if !resp.status().is_success() {
+                return Err(ErrorKind::Io.custom(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!(
+                        "unexpected HTTP status {} from JSON-RPC call",
+                        resp.status()
+                    ),
+                )));
+            }
+
+            resp.json::<Value>()
+                .await
+                .map_err(|e| ErrorKind::Io.full("failed to decode JSON-RPC response", e))
+        })
+    }
+}
+
+/// [`Runtime`] backed by `async-std`. Selected by the `async-std` Cargo feature.
+#[cfg(feature = "async-std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct AsyncStdRuntime;
+
+#[cfg(feature = "async-std")]
+impl Runtime for AsyncStdRuntime {
+    fn spawn(
+        &self,
+        fut: Pin<Box<dyn Future<Output = ()> + Send>>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async_std::task::spawn(fut))
+    }
+
+    fn sleep(&self, duration: Duration) -> Pin<Box<dyn Future<Output = ()> + Send>> {
+        Box::pin(async_std::task::sleep(duration))
+    }
+
+    fn tcp_connect(&self, addr: SocketAddr) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> {
+        Box::pin(async move {
+            async_std::net::TcpStream::connect(addr)
+                .await
+                .map(drop)
+                .map_err(|e| ErrorKind::Io.custom(e))
+        })
+    }
+
+    fn http_post_json(
+        &self,
+        url: Url,
+        body: Value,
+    ) -> Pin<Box<dyn Future<Output = Result<Value>> + Send>> {
+        Box::pin(async move {
+            let mut resp = surf::post(url.as_str())
+                .body_json(&body)
+                .map_err(|e| ErrorKind::Io.full("failed to encode JSON-RPC body", e))?
+                .await
+                .map_err(|e| ErrorKind::Io.full("transport error performing JSON-RPC call", e))?;
+
+            if !resp.status().is_success() {
+                return Err(ErrorKind::Io.custom(std::io::Error::new(
+                    std::io::ErrorKind::Other,
+                    format!(
+                        "unexpected HTTP status {} from JSON-RPC call",
+                        resp.status()
+                    ),
+                )));
+            }
+
+            resp.body_json::<Value>()
+                .await
+                .map_err(|e| ErrorKind::Io.full("failed to decode JSON-RPC response", e))
+        })
+    }
+}
+
+/// The [`Runtime`] selected at compile time via Cargo features.
+#[cfg(feature = "tokio")]
+pub(crate) fn default_runtime() -> impl Runtime {
+    TokioRuntime
+}
+
+#[cfg(all(feature = "async-std", not(feature = "tokio")))]
+pub(crate) fn default_runtime() -> impl Runtime {
+    AsyncStdRuntime
+}